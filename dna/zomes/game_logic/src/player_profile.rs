@@ -1,8 +1,50 @@
 use crate::game_code::get_game_anchor;
+use hdk::hash_path::path::Path;
 use hdk::prelude::*;
+use std::collections::BTreeSet;
+use std::collections::HashSet;
 
 pub const PLAYER_LINK_TAG: &str = "PLAYER";
 
+/// Tag for the link from a game anchor to the AgentPubKey of that game's
+/// host, i.e. the first agent who joined it. See `grant_host_authority`.
+pub const HOST_LINK_TAG: &str = "HOST";
+/// Name of the zome fn a host's CapGrant authorizes. Kept as a constant so
+/// the grant and the `#[hdk_extern]` wrapper in lib.rs can't drift apart.
+pub const REMOVE_PLAYER_FN: &str = "remove_player";
+/// Tag on the CapGrant `grant_host_authority` creates. `remove_player`
+/// checks the call it's executing under carries a grant with this same
+/// tag (see `cap_grant_authorizes`) before trusting the HOST link.
+pub const HOST_CAP_GRANT_TAG: &str = "game host";
+
+/// Tag for the link from an agent key to each PlayerProfile entry they've
+/// authored. Lets the social graph below resolve a bare AgentPubKey back to
+/// profile data.
+pub const PROFILE_LINK_TAG: &str = "PROFILE";
+/// Tag for the link from a follower's profile hash to the profile hash of
+/// the player they follow. See `follow`/`unfollow`.
+pub const FOLLOW_LINK_TAG: &str = "FOLLOW";
+/// Tag for the reverse of FOLLOW_LINK_TAG, from a followee's profile hash
+/// back to each of their followers. Kept as its own link so `get_followers`
+/// doesn't need a full DHT scan to answer "who follows this player".
+pub const FOLLOWER_LINK_TAG: &str = "FOLLOWER";
+
+/// Prefix for the deterministic anchor path a logical player's identity
+/// lives under, e.g. `identity.alice`. See `register_identity`.
+pub const IDENTITY_PATH_PREFIX: &str = "identity";
+/// Tag for the link from an agent key to the identity anchor it's
+/// currently attached to.
+pub const IDENTITY_KEY_LINK_TAG: &str = "IDENTITY_KEY";
+
+/// Path of the single, global anchor every game gets linked under once it
+/// has its first player, so the whole set of active games can be listed
+/// without already knowing their codes. See `list_active_games`.
+pub const GAMES_REGISTRY_PATH: &str = "games";
+
+/// Upper bound on how long a nickname is allowed to be. Keeps a malicious
+/// agent from stuffing an oversized string into an otherwise-tiny entry.
+pub const MAX_NICKNAME_LENGTH: usize = 50;
+
 /// This is a Rust structure which represents an actual
 /// Holochain entry that stores user's profile for the specific game
 /// First we derive just a Rust struct, and then we apply hdk_entry
@@ -30,9 +72,106 @@ pub struct JoinGameInfo {
     pub player_nickname: String,
 }
 
+/// One row of the lobby browser: a game's code and how many players are
+/// currently in it. Returned by `list_active_games`.
+#[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes)]
+pub struct GameSummary {
+    pub game_code: String,
+    pub player_count: usize,
+}
+
+/// Real-time signals emitted by this module. The UI subscribes to these
+/// (via the conductor's app signal stream) so it doesn't have to poll
+/// `get_game_players` to notice changes to the lobby roster.
+#[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes)]
+pub enum GameSignal {
+    PlayerJoined {
+        game_code: String,
+        player_profile: PlayerProfile,
+    },
+}
+
+/// Handles a `GameSignal` sent to us via `remote_signal` by another agent
+/// (see `notify_players_joined`) and re-emits it as a local signal, which is
+/// what the UI actually subscribes to. Wired up to the `recv_remote_signal`
+/// callback in lib.rs.
+pub fn recv_remote_signal(signal: SerializedBytes) -> ExternResult<()> {
+    let game_signal: GameSignal = signal.try_into()?;
+    debug!("recv_remote_signal | game_signal: {:?}", game_signal);
+    emit_signal(&game_signal)
+}
+
+/// Builds the deterministic anchor path for a user-chosen identity string.
+/// Every key an agent has ever authenticated with can link into this same
+/// path, which is how a returning agent with a brand new
+/// `agent_initial_pubkey` (say, after reinstalling the app, see the caveat
+/// on `create_and_hash_entry_player_profile` below) re-attaches to the same
+/// logical player instead of starting from a blank profile.
+fn identity_path(identity: &str) -> Path {
+    Path::from(format!("{}.{}", IDENTITY_PATH_PREFIX, identity))
+}
+
+/// Registers a durable identity for the calling agent and links their
+/// current key to it. Safe to call again later (e.g. after a reinstall)
+/// with the same `identity` string: the anchor path is deterministic, so
+/// this just attaches the new key to the identity that's already there.
+///
+/// Accepted gap: because the anchor path is deterministic and any key may
+/// legitimately attach to it (that's the whole point - it's how a
+/// reinstalled agent's new key re-attaches), this is first-come-first-served
+/// with no way to tell a genuine reattachment from an unrelated agent
+/// squatting on someone else's chosen string first. A squatter's key then
+/// shares the real owner's dedupe key in `get_player_profiles_for_anchor`,
+/// which keeps only the first profile it finds per identity, so in a game
+/// both have joined the real owner can vanish from the roster. Closing this needs a way to prove a
+/// new key belongs to the same logical agent as an anchor's existing keys
+/// (e.g. a signature chain), which this zome doesn't have yet - same shape
+/// of gap as the "best-effort" host race noted on `validate_create_link_host`.
+pub fn register_identity(identity: String) -> ExternResult<EntryHash> {
+    let path = identity_path(&identity);
+    path.ensure()?;
+    let identity_anchor = path.hash()?;
+    link_current_key_to_identity(identity_anchor.clone())?;
+    Ok(identity_anchor)
+}
+
+/// Links the agent key we're currently running as to an identity anchor, so
+/// `resolve_identity_for_key` can walk back from this key to the identity
+/// even after the agent rotates keys.
+pub fn link_current_key_to_identity(identity_anchor: EntryHash) -> ExternResult<HeaderHash> {
+    let agent_key = agent_info()?.agent_initial_pubkey;
+    debug!(
+        "link_current_key_to_identity | agent_key: {:?}, identity_anchor: {:?}",
+        agent_key, identity_anchor
+    );
+    create_link(
+        agent_key.into(),
+        identity_anchor,
+        LinkTag::new(String::from(IDENTITY_KEY_LINK_TAG)),
+    )
+}
+
+/// Walks the key -> identity link for `agent_key`, if one was ever created
+/// via `link_current_key_to_identity`. Agents who never called
+/// `register_identity` simply have no such link, which is fine: they're
+/// treated as their own, single-key identity.
+fn resolve_identity_for_key(agent_key: AgentPubKey) -> ExternResult<Option<EntryHash>> {
+    let identity_links = get_links(
+        agent_key.into(),
+        Some(LinkTag::new(String::from(IDENTITY_KEY_LINK_TAG))),
+    )?;
+    Ok(identity_links
+        .into_inner()
+        .into_iter()
+        .next()
+        .map(|link| link.target.into()))
+}
+
 /// Creates a PlayerProfile instance, commits it as a Holochain entry
-/// and returns a hash value of this entry
-pub fn create_and_hash_entry_player_profile(player_nickname: String) -> ExternResult<EntryHash> {
+/// and returns the profile itself along with a hash value of this entry
+pub fn create_and_hash_entry_player_profile(
+    player_nickname: String,
+) -> ExternResult<(PlayerProfile, EntryHash)> {
     // Retrieve info about an agent who is currently executing this code
     // For every instance of the app this would produce different results.
     let player_agent = agent_info()?;
@@ -51,6 +190,9 @@ pub fn create_and_hash_entry_player_profile(player_nickname: String) -> ExternRe
         // Beware: this is bad design for real apps, because:
         // 1/ initial_pubkey is linked to app itself, so no roaming profile
         // 2/ lost if app is reinstalled (= that would be basically a new user)
+        // Mitigated by the identity layer above: an agent who calls
+        // register_identity keeps turning up as the same logical player
+        // across keys, even though each key still gets its own profile entry.
         id: player_agent.agent_initial_pubkey,
         nickname: player_nickname,
     };
@@ -65,8 +207,16 @@ pub fn create_and_hash_entry_player_profile(player_nickname: String) -> ExternRe
     debug!("create_and_hash_entry_player_profile | profile created, hashing");
     // Calculate a hash value of the entry we just written to DHT:
     // that would be essentially ID of that piece of information.
-    // And since there's no ; in the end, this is what we return from current fn
-    hash_entry(&player_profile)
+    let player_profile_entry_hash = hash_entry(&player_profile)?;
+    // Index this profile under the key that authored it, so the social
+    // graph (follow/unfollow, see below) can resolve a bare AgentPubKey
+    // back to the PlayerProfile(s) it's associated with.
+    create_link(
+        player_profile.id.clone().into(),
+        player_profile_entry_hash.clone().into(),
+        LinkTag::new(String::from(PROFILE_LINK_TAG)),
+    )?;
+    Ok((player_profile, player_profile_entry_hash))
 }
 
 /// Creates user's profile for the game and registers this user as one of the game players
@@ -79,11 +229,29 @@ pub fn join_game(game_info: JoinGameInfo) -> ExternResult<EntryHash> {
     // Another example of logs output with a different priority level
     info!("join_game_with_code | game_info: {:?}", game_info);
     // Retrieve an anchor for the game code provided in input
-    let game_anchor = get_game_anchor(game_info.game_code)?;
+    let game_code = game_info.game_code.clone();
+    let game_anchor = get_game_anchor(game_code)?;
     debug!("join_game_with_code | anchor created {:?}", &game_anchor);
+    // Before we add the newcomer's link, grab everyone who's already in this
+    // game so we know who to notify about the new arrival further down.
+    let existing_player_profiles = get_player_profiles_for_anchor(game_anchor.clone())?;
+    // No host yet means this join is standing up the game anchor for
+    // the first time: this agent becomes its host and is granted
+    // moderation authority over it, and the game itself becomes visible to
+    // list_active_games
+    let has_host = !get_links(
+        game_anchor.clone(),
+        Some(LinkTag::new(String::from(HOST_LINK_TAG))),
+    )?
+    .into_inner()
+    .is_empty();
+    if !has_host {
+        grant_host_authority(game_anchor.clone())?;
+        register_active_game(&game_info.game_code, game_anchor.clone())?;
+    }
     // Create player's profile. So far it isn't connected to anything,
     // just a combination of nickname & pub key
-    let player_profile_entry_hash =
+    let (player_profile, player_profile_entry_hash) =
         create_and_hash_entry_player_profile(game_info.player_nickname)?;
     debug!(
         "join_game_with_code | profile entry hash {:?}",
@@ -98,16 +266,317 @@ pub fn join_game(game_info: JoinGameInfo) -> ExternResult<EntryHash> {
         LinkTag::new(String::from(PLAYER_LINK_TAG)),
     )?;
     debug!("join_game_with_code | link created");
+    // Let everyone else already in the game know a new player just joined,
+    // so their lobby roster can update live instead of having to poll
+    // get_game_players
+    notify_players_joined(game_info.game_code, player_profile, existing_player_profiles)?;
     // Return entry hash of the anchor wrapped in ExternResult::Ok variant
     Ok(game_anchor)
 }
 
+/// Establishes the calling agent as the host of `game_anchor` by linking
+/// their key to it, then commits a CapGrant authorizing that same agent to
+/// call `remove_player` - the privileged, moderation-only counterpart to the
+/// wide-open `join_game`.
+fn grant_host_authority(game_anchor: EntryHash) -> ExternResult<()> {
+    let host = agent_info()?.agent_latest_pubkey;
+    debug!("grant_host_authority | game_anchor: {:?}, host: {:?}", game_anchor, host);
+    create_link(
+        game_anchor,
+        host.clone().into(),
+        LinkTag::new(String::from(HOST_LINK_TAG)),
+    )?;
+
+    let mut functions: BTreeSet<(ZomeName, FunctionName)> = BTreeSet::new();
+    functions.insert((zome_info()?.name, FunctionName::from(REMOVE_PLAYER_FN)));
+
+    create_cap_grant(CapGrantEntry {
+        tag: String::from(HOST_CAP_GRANT_TAG),
+        access: CapAccess::Assigned {
+            secret: generate_cap_secret()?,
+            assignees: vec![host].into_iter().collect(),
+        },
+        functions: GrantedFunctions::Listed(functions),
+    })?;
+
+    Ok(())
+}
+
+/// Hash of the single, global anchor every active game is linked under.
+/// Commits the anchor's path entry if it isn't already present, so this is
+/// only safe to call from a zome fn with write access to the source chain -
+/// never from `validate()`, which runs read-only. See
+/// `games_registry_anchor_hash` for the validation-safe equivalent.
+fn games_registry_anchor() -> ExternResult<EntryHash> {
+    let path = Path::from(GAMES_REGISTRY_PATH);
+    path.ensure()?;
+    path.hash()
+}
+
+/// Pure hash of the games registry anchor path, with no `path.ensure()`
+/// write. `Path::hash` is a deterministic function of the path's
+/// components, so this returns the same `EntryHash` `games_registry_anchor`
+/// does regardless of whether the anchor entry has been committed yet -
+/// which is exactly what validation, running with read-only DHT access,
+/// needs to compare a link's base address against.
+fn games_registry_anchor_hash() -> ExternResult<EntryHash> {
+    Path::from(GAMES_REGISTRY_PATH).hash()
+}
+
+/// Links `game_anchor` under the global games registry anchor, tagging the
+/// link with the game's own code so `list_active_games` can report it
+/// without having to reverse a hash back into a code.
+fn register_active_game(game_code: &str, game_anchor: EntryHash) -> ExternResult<()> {
+    let registry_anchor = games_registry_anchor()?;
+    debug!(
+        "register_active_game | game_code: {}, game_anchor: {:?}",
+        game_code, game_anchor
+    );
+    create_link(
+        registry_anchor,
+        game_anchor,
+        LinkTag::new(game_code.as_bytes().to_vec()),
+    )?;
+    Ok(())
+}
+
+/// Lists every game that has had at least one player join, along with how
+/// many players are currently in it. Powers a lobby browser that doesn't
+/// need to already know a game code to discover it.
+pub fn list_active_games() -> ExternResult<Vec<GameSummary>> {
+    let registry_anchor = games_registry_anchor()?;
+    let game_links = get_links(registry_anchor, None)?;
+    debug!("list_active_games | game_links: {:?}", game_links);
+
+    let mut summaries = vec![];
+    for link in game_links.into_inner() {
+        let game_code = String::from_utf8(link.tag.into_inner())
+            .map_err(|e| WasmError::Guest(e.to_string()))?;
+        let player_links = get_links(
+            link.target.into(),
+            Some(LinkTag::new(String::from(PLAYER_LINK_TAG))),
+        )?;
+        summaries.push(GameSummary {
+            game_code,
+            player_count: player_links.into_inner().len(),
+        });
+    }
+
+    Ok(summaries)
+}
+
+/// Whether `agent` is the HOST_LINK_TAG-linked host of `game_anchor`. Only
+/// half of the authorization story: see `cap_grant_authorizes` for the
+/// other half, which checks the call actually carries the host's CapGrant
+/// rather than just matching a link.
+fn is_game_host(game_anchor: EntryHash, agent: &AgentPubKey) -> ExternResult<bool> {
+    let host_links = get_links(game_anchor, Some(LinkTag::new(String::from(HOST_LINK_TAG))))?;
+    Ok(host_links
+        .into_inner()
+        .into_iter()
+        .any(|link| EntryHash::from(link.target) == EntryHash::from(agent.clone())))
+}
+
+/// Whether `cap_grant` - the grant this zome call is actually executing
+/// under, per `call_info()` - authorizes the privileged action tagged
+/// `required_tag`. A call made directly on our own source chain
+/// (`ChainAuthor`) is always authorized, since nobody else could have
+/// placed it there; a call arriving via `call_remote` only counts if it
+/// came in under a `CapGrant` we tagged for this purpose.
+fn cap_grant_authorizes(cap_grant: &CapGrant, required_tag: &str) -> bool {
+    match cap_grant {
+        CapGrant::ChainAuthor(_) => true,
+        CapGrant::RemoteAgent(zome_call_cap_grant) => zome_call_cap_grant.tag == required_tag,
+    }
+}
+
+/// Whether the current zome call is authorized to moderate `game_anchor`.
+/// This is the real check `remove_player` gates on: being linked as host
+/// (`is_game_host`) is necessary but not sufficient on its own, since link
+/// creation alone can't be fully trusted (see `validate_create_link_host`
+/// for the best-effort restriction on that); actually holding the host's
+/// CapGrant (`cap_grant_authorizes`) is what this was built for.
+fn caller_is_authorized_host(game_anchor: EntryHash, call_info: &CallInfo) -> ExternResult<bool> {
+    let is_linked_host = is_game_host(game_anchor, &call_info.provenance)?;
+    Ok(is_linked_host && cap_grant_authorizes(&call_info.cap_grant, HOST_CAP_GRANT_TAG))
+}
+
+/// Removes a player from a game by deleting the anchor -> PlayerProfile
+/// link registered for them in `join_game`. This is the privileged action
+/// the CapGrant from `grant_host_authority` exists for: only a call that is
+/// both linked as, and actually authorized under the CapGrant of, the
+/// game's host may go through.
+pub fn remove_player(game_code: String, player_profile_hash: EntryHash) -> ExternResult<()> {
+    let call_info = call_info()?;
+    let game_anchor = get_game_anchor(game_code)?;
+    debug!(
+        "remove_player | call_info: {:?}, game_anchor: {:?}, player_profile_hash: {:?}",
+        call_info, game_anchor, player_profile_hash
+    );
+
+    if !caller_is_authorized_host(game_anchor.clone(), &call_info)? {
+        return Err(WasmError::Guest(String::from(
+            "only the game host may remove a player",
+        )));
+    }
+
+    let player_links = get_links(
+        game_anchor,
+        Some(LinkTag::new(String::from(PLAYER_LINK_TAG))),
+    )?;
+    for link in player_links.into_inner() {
+        if EntryHash::from(link.target.clone()) == player_profile_hash {
+            delete_link(link.create_link_hash)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolves the most recently authored PlayerProfile entry for `agent`, via
+/// the PROFILE_LINK_TAG index created in `create_and_hash_entry_player_profile`.
+/// An agent can have more than one profile (one per game they've joined);
+/// the social graph below is anchored on whichever is newest.
+fn latest_profile_hash_for_agent(agent: AgentPubKey) -> ExternResult<EntryHash> {
+    let profile_links = get_links(
+        agent.clone().into(),
+        Some(LinkTag::new(String::from(PROFILE_LINK_TAG))),
+    )?;
+    profile_links
+        .into_inner()
+        .into_iter()
+        .last()
+        .map(|link| EntryHash::from(link.target))
+        .ok_or_else(|| WasmError::Guest(format!("{:?} has no player profile yet", agent)))
+}
+
+/// Retrieves and deserializes the PlayerProfile entry at `entry_hash`.
+fn get_player_profile(entry_hash: EntryHash) -> ExternResult<PlayerProfile> {
+    let element: Element = get(entry_hash, GetOptions::default())?
+        .ok_or(WasmError::Guest(String::from("Entry not found")))?;
+    element.entry().to_app_option()?.ok_or(WasmError::Guest(
+        "The targeted entry is not a PlayerProfile".into(),
+    ))
+}
+
+/// Makes the calling agent follow `target`, independent of any game. Mirrors
+/// the Junto-style social graph: identity is a stable key, and following is
+/// a link on top of it rather than anything tied to a particular game code.
+pub fn follow(target: AgentPubKey) -> ExternResult<HeaderHash> {
+    let caller = agent_info()?.agent_initial_pubkey;
+    let caller_profile_hash = latest_profile_hash_for_agent(caller)?;
+    let target_profile_hash = latest_profile_hash_for_agent(target)?;
+    debug!(
+        "follow | caller_profile_hash: {:?}, target_profile_hash: {:?}",
+        caller_profile_hash, target_profile_hash
+    );
+    // Reverse link first, so get_followers is never missing an entry that
+    // get_following already reports
+    create_link(
+        target_profile_hash.clone(),
+        caller_profile_hash.clone(),
+        LinkTag::new(String::from(FOLLOWER_LINK_TAG)),
+    )?;
+    create_link(
+        caller_profile_hash,
+        target_profile_hash,
+        LinkTag::new(String::from(FOLLOW_LINK_TAG)),
+    )
+}
+
+/// Undoes a previous `follow(target)` by the calling agent.
+pub fn unfollow(target: AgentPubKey) -> ExternResult<()> {
+    let caller = agent_info()?.agent_initial_pubkey;
+    let caller_profile_hash = latest_profile_hash_for_agent(caller)?;
+    let target_profile_hash = latest_profile_hash_for_agent(target)?;
+
+    let follow_links = get_links(
+        caller_profile_hash.clone(),
+        Some(LinkTag::new(String::from(FOLLOW_LINK_TAG))),
+    )?;
+    for link in follow_links.into_inner() {
+        if EntryHash::from(link.target.clone()) == target_profile_hash {
+            delete_link(link.create_link_hash)?;
+        }
+    }
+
+    let follower_links = get_links(
+        target_profile_hash,
+        Some(LinkTag::new(String::from(FOLLOWER_LINK_TAG))),
+    )?;
+    for link in follower_links.into_inner() {
+        if EntryHash::from(link.target.clone()) == caller_profile_hash {
+            delete_link(link.create_link_hash)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Players that `agent` follows.
+pub fn get_following(agent: AgentPubKey) -> ExternResult<Vec<PlayerProfile>> {
+    let profile_hash = latest_profile_hash_for_agent(agent)?;
+    let follow_links = get_links(profile_hash, Some(LinkTag::new(String::from(FOLLOW_LINK_TAG))))?;
+    follow_links
+        .into_inner()
+        .into_iter()
+        .map(|link| get_player_profile(link.target.into()))
+        .collect()
+}
+
+/// Players that follow `agent`.
+pub fn get_followers(agent: AgentPubKey) -> ExternResult<Vec<PlayerProfile>> {
+    let profile_hash = latest_profile_hash_for_agent(agent)?;
+    let follower_links = get_links(
+        profile_hash,
+        Some(LinkTag::new(String::from(FOLLOWER_LINK_TAG))),
+    )?;
+    follower_links
+        .into_inner()
+        .into_iter()
+        .map(|link| get_player_profile(link.target.into()))
+        .collect()
+}
+
+/// Broadcasts a `PlayerJoined` signal to every player already registered
+/// for `game_code`, so lobbies can update in real time instead of polling
+/// `get_game_players`. Also emits it locally, so the joining agent's own
+/// UI updates immediately instead of only hearing about everyone else.
+fn notify_players_joined(
+    game_code: String,
+    player_profile: PlayerProfile,
+    existing_player_profiles: Vec<PlayerProfile>,
+) -> ExternResult<()> {
+    let signal = GameSignal::PlayerJoined {
+        game_code,
+        player_profile,
+    };
+    let recipients: Vec<AgentPubKey> = existing_player_profiles
+        .into_iter()
+        .map(|player_profile| player_profile.id)
+        .collect();
+    debug!(
+        "notify_players_joined | signal: {:?}, recipients: {:?}",
+        signal, recipients
+    );
+    emit_signal(&signal)?;
+    remote_signal(SerializedBytes::try_from(signal)?, recipients)
+}
+
 /// Retrieves player profiles that are linked to the anchor for the provided
 /// short_unique_code.
 pub fn get_game_players(game_code: String) -> ExternResult<Vec<PlayerProfile>> {
     // Retrieve entry hash of our game code anchor
     let game_anchor = get_game_anchor(game_code)?;
     debug!("anchor: {:?}", game_anchor);
+    get_player_profiles_for_anchor(game_anchor)
+}
+
+/// Shared by `get_game_players` and `join_game`: resolves the PlayerProfiles
+/// linked to a given game anchor with a PLAYER_LINK_TAG link, keeping only
+/// the first one found per registered identity (or per key, for keys with
+/// no identity).
+fn get_player_profiles_for_anchor(game_anchor: EntryHash) -> ExternResult<Vec<PlayerProfile>> {
     // Retrieve a set of links that have anchor as a base, with the tag PLAYER_LINK_TAG
     let player_links: Links = get_links(
         game_anchor,
@@ -120,6 +589,10 @@ pub fn get_game_players(game_code: String) -> ExternResult<Vec<PlayerProfile>> {
     // First, create a buffer vec for our results. Make it mutable so we
     // can add results one-by-one later
     let mut players = vec![];
+    // Multiple keys can map to the same registered identity (see
+    // register_identity); track which identities/keys we've already added
+    // so the same logical player doesn't show up twice.
+    let mut seen = HashSet::new();
     // Iterate through all the links contained inside the link instance
     for link in player_links.into_inner() {
         debug!("link: {:?}", link);
@@ -136,10 +609,461 @@ pub fn get_game_players(game_code: String) -> ExternResult<Vec<PlayerProfile>> {
         let player_profile: PlayerProfile = entry_option.ok_or(WasmError::Guest(
             "The targeted entry is not agent pubkey".into(),
         ))?;
-        // Add this PlayerProfile to our results vector
-        players.push(player_profile);
+        // Use the registered identity as the dedupe key if this key has one,
+        // otherwise fall back to the key itself
+        let dedupe_key = resolve_identity_for_key(player_profile.id.clone())?
+            .unwrap_or_else(|| player_profile.id.clone().into());
+        // Add this PlayerProfile to our results vector, unless we've
+        // already seen its identity/key
+        if seen.insert(dedupe_key) {
+            players.push(player_profile);
+        }
     }
 
     // wrap our vector into ExternResult and return
     Ok(players)
 }
+
+/// Validation callback for `PlayerProfile` entry creation (and updates, since
+/// both go through the same header shape). This is where we close the hole
+/// called out above in `create_and_hash_entry_player_profile`: without it,
+/// any agent could commit a profile claiming to be someone else's key, or
+/// with a blank/oversized nickname, and the DHT would happily store it.
+///
+/// Called from the `validate` dispatch below; wired up to the `validate`
+/// callback itself in lib.rs like the other zome entry points.
+pub fn validate_create_entry_player_profile(
+    validate_data: ValidateData,
+) -> ExternResult<ValidateCallbackResult> {
+    let element = validate_data.element;
+
+    let player_profile: PlayerProfile = match element.entry().to_app_option()? {
+        Some(player_profile) => player_profile,
+        // Not a PlayerProfile entry (or malformed) - nothing for us to validate here
+        None => return Ok(ValidateCallbackResult::Valid),
+    };
+
+    // 1/ the profile must be keyed on the agent that authored it, otherwise
+    // anyone could commit a profile impersonating another player
+    let author = element.header().author();
+    if &player_profile.id != author {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "player_profile.id must match the public key of the agent that authored it",
+        )));
+    }
+
+    // 2/ nickname must be present...
+    if player_profile.nickname.trim().is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "player_profile.nickname must not be blank",
+        )));
+    }
+
+    // ...and not absurdly long
+    if player_profile.nickname.chars().count() > MAX_NICKNAME_LENGTH {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "player_profile.nickname must be {} characters or fewer",
+            MAX_NICKNAME_LENGTH
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Validation callback for the anchor -> PlayerProfile link created in
+/// `join_game`. Confirms the link actually points at a `PlayerProfile`
+/// entry, so `get_game_players` can never be tricked into resolving (and
+/// erroring out on, or worse trusting) a link to unrelated data.
+pub fn validate_create_link_player_profile(
+    validate_data: ValidateData,
+) -> ExternResult<ValidateCallbackResult> {
+    let element = validate_data.element;
+
+    let create_link = match element.header() {
+        Header::CreateLink(create_link) => create_link,
+        // Not a link creation - nothing for us to validate here
+        _ => return Ok(ValidateCallbackResult::Valid),
+    };
+
+    if create_link.tag != LinkTag::new(String::from(PLAYER_LINK_TAG)) {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+
+    let target: EntryHash = create_link.target_address.clone().into();
+    let targeted_element = match get(target.clone(), GetOptions::default())? {
+        Some(targeted_element) => targeted_element,
+        // Not finding the target yet doesn't mean the link is bad - it may
+        // simply not have propagated to us yet. Ask to be revalidated once
+        // it has, rather than false-rejecting a legitimate join.
+        None => return Ok(ValidateCallbackResult::UnresolvedDependencies(vec![target.into()])),
+    };
+
+    match targeted_element.entry().to_app_option::<PlayerProfile>()? {
+        Some(_) => Ok(ValidateCallbackResult::Valid),
+        None => Ok(ValidateCallbackResult::Invalid(String::from(
+            "PLAYER link target does not resolve to a PlayerProfile entry",
+        ))),
+    }
+}
+
+/// Validation callback for the agent key -> PlayerProfile link created in
+/// `create_and_hash_entry_player_profile`. Without this, any agent could
+/// `create_link(victim_key, attacker_profile, PROFILE_LINK_TAG)` directly and
+/// hijack `latest_profile_hash_for_agent(victim)`, which `follow`/`unfollow`/
+/// `get_following`/`get_followers` all resolve through.
+pub fn validate_create_link_profile(
+    validate_data: ValidateData,
+) -> ExternResult<ValidateCallbackResult> {
+    let element = validate_data.element;
+
+    let create_link = match element.header() {
+        Header::CreateLink(create_link) => create_link,
+        // Not a link creation - nothing for us to validate here
+        _ => return Ok(ValidateCallbackResult::Valid),
+    };
+
+    let author: AnyLinkableHash = element.header().author().clone().into();
+    if create_link.base_address != author {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "a PROFILE link's base must be the authoring agent's own key",
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Looks up the `PlayerProfile` at `address` (if it's resolvable yet) and
+/// returns the key it's keyed on. Shared by the FOLLOW/FOLLOWER validators
+/// below, which both need to confirm a profile hash actually belongs to the
+/// agent authoring the link.
+fn resolve_profile_owner(address: AnyLinkableHash) -> ExternResult<Option<AgentPubKey>> {
+    let entry_hash: EntryHash = address.into();
+    match get(entry_hash, GetOptions::default())? {
+        Some(element) => Ok(element
+            .entry()
+            .to_app_option::<PlayerProfile>()?
+            .map(|player_profile| player_profile.id)),
+        None => Ok(None),
+    }
+}
+
+/// Validation callback for the FOLLOW_LINK_TAG link created in `follow`.
+/// Without this, a third party could forge `create_link(attacker_profile,
+/// victim_profile, FOLLOW_LINK_TAG)` and claim the attacker follows someone
+/// they never chose to. The base must resolve to a profile actually owned by
+/// the authoring agent.
+pub fn validate_create_link_follow(
+    validate_data: ValidateData,
+) -> ExternResult<ValidateCallbackResult> {
+    let element = validate_data.element;
+
+    let create_link = match element.header() {
+        Header::CreateLink(create_link) => create_link,
+        // Not a link creation - nothing for us to validate here
+        _ => return Ok(ValidateCallbackResult::Valid),
+    };
+
+    let author = element.header().author().clone();
+    match resolve_profile_owner(create_link.base_address.clone())? {
+        Some(owner) if owner == author => Ok(ValidateCallbackResult::Valid),
+        Some(_) => Ok(ValidateCallbackResult::Invalid(String::from(
+            "a FOLLOW link's base must be a profile owned by the authoring agent",
+        ))),
+        // Not finding the base profile yet doesn't mean the link is bad - it
+        // may simply not have propagated to us yet. Ask to be revalidated once
+        // it has, rather than false-rejecting a legitimate follow.
+        None => Ok(ValidateCallbackResult::UnresolvedDependencies(vec![
+            create_link.base_address.clone(),
+        ])),
+    }
+}
+
+/// Validation callback for the FOLLOWER_LINK_TAG link created in `follow`.
+/// The reverse of `validate_create_link_follow`: here it's the *target* that
+/// must resolve to a profile owned by the authoring agent, since this link
+/// runs followee -> follower and the follower is the one doing the linking.
+pub fn validate_create_link_follower(
+    validate_data: ValidateData,
+) -> ExternResult<ValidateCallbackResult> {
+    let element = validate_data.element;
+
+    let create_link = match element.header() {
+        Header::CreateLink(create_link) => create_link,
+        // Not a link creation - nothing for us to validate here
+        _ => return Ok(ValidateCallbackResult::Valid),
+    };
+
+    let author = element.header().author().clone();
+    match resolve_profile_owner(create_link.target_address.clone())? {
+        Some(owner) if owner == author => Ok(ValidateCallbackResult::Valid),
+        Some(_) => Ok(ValidateCallbackResult::Invalid(String::from(
+            "a FOLLOWER link's target must be a profile owned by the authoring agent",
+        ))),
+        None => Ok(ValidateCallbackResult::UnresolvedDependencies(vec![
+            create_link.target_address.clone(),
+        ])),
+    }
+}
+
+/// Validation callback for the agent key -> identity anchor link created by
+/// `link_current_key_to_identity`. Without this, any agent could link
+/// *another* agent's key into an identity anchor they don't control, and
+/// `resolve_identity_for_key` (which every peer's `get_player_profiles_for_anchor`
+/// relies on to de-dup the roster) would trust it regardless.
+///
+/// Doesn't and can't stop identity squatting - see the accepted-gap note on
+/// `register_identity`: this only confirms a link's base is the author's own
+/// key, not that the author is entitled to the identity string they're
+/// claiming.
+pub fn validate_create_link_identity_key(
+    validate_data: ValidateData,
+) -> ExternResult<ValidateCallbackResult> {
+    let element = validate_data.element;
+
+    let create_link = match element.header() {
+        Header::CreateLink(create_link) => create_link,
+        // Not a link creation - nothing for us to validate here
+        _ => return Ok(ValidateCallbackResult::Valid),
+    };
+
+    let author: AnyLinkableHash = element.header().author().clone().into();
+    if create_link.base_address != author {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "an IDENTITY_KEY link's base must be the authoring agent's own key",
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Validation callback for the game anchor -> AgentPubKey link created by
+/// `grant_host_authority`. Two rules: an agent can only link *itself* in as
+/// host, never someone else, and - best-effort - a game anchor can't pick
+/// up a second, different host once it already has one.
+pub fn validate_create_link_host(
+    validate_data: ValidateData,
+) -> ExternResult<ValidateCallbackResult> {
+    let element = validate_data.element;
+
+    let create_link = match element.header() {
+        Header::CreateLink(create_link) => create_link,
+        // Not a link creation - nothing for us to validate here
+        _ => return Ok(ValidateCallbackResult::Valid),
+    };
+
+    let author: AnyLinkableHash = element.header().author().clone().into();
+    if create_link.target_address != author {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "a HOST link may only name the authoring agent as host",
+        )));
+    }
+
+    // Best-effort only: this catches a second host link once it's reached
+    // the validating node, but two simultaneous "first" joins racing each
+    // other can still both pass. It narrows the hole chunk0-4's review
+    // flagged; closing it fully needs a single-writer anchor pattern this
+    // zome doesn't have yet.
+    let existing_host_links = get_links(
+        create_link.base_address.clone(),
+        Some(LinkTag::new(String::from(HOST_LINK_TAG))),
+    )?;
+    let already_has_different_host = existing_host_links
+        .into_inner()
+        .into_iter()
+        .any(|link| link.target != create_link.target_address);
+    if already_has_different_host {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "this game anchor already has a different host",
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Validation callback for the games registry anchor -> game anchor link
+/// created by `register_active_game`. Unlike the other link validators
+/// here, this one isn't dispatched by tag (the tag is the game code itself,
+/// which varies per link) but by base address in `validate` below. Confirms
+/// the target is actually the anchor `get_game_anchor` derives from the
+/// link's own tag, so `list_active_games` can't be polluted with a listing
+/// that points at a garbage or unrelated hash.
+pub fn validate_create_link_game_registry(
+    validate_data: ValidateData,
+) -> ExternResult<ValidateCallbackResult> {
+    let element = validate_data.element;
+
+    let create_link = match element.header() {
+        Header::CreateLink(create_link) => create_link,
+        // Not a link creation - nothing for us to validate here
+        _ => return Ok(ValidateCallbackResult::Valid),
+    };
+
+    let game_code = match String::from_utf8(create_link.tag.clone().into_inner()) {
+        Ok(game_code) => game_code,
+        Err(_) => {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "a games registry link's tag must be a valid UTF-8 game code",
+            )))
+        }
+    };
+    let expected_target: AnyLinkableHash = get_game_anchor(game_code)?.into();
+    if create_link.target_address != expected_target {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "a games registry link's target must be the anchor derived from its own tag",
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Which module-level link validator a link's tag corresponds to. Kept as
+/// its own pure classification, separate from `validate`'s dispatch below,
+/// so the tag-matching order - in particular, that PLAYER_LINK_TAG (the tag
+/// `join_game` creates on every single player join) is classified ahead of
+/// `Other` - can be unit tested without a host context. `Other` is the only
+/// case `validate` needs to do any further, host-call-requiring work for,
+/// to tell a games-registry link from anything else.
+#[derive(Debug, PartialEq, Eq)]
+enum LinkTagKind {
+    IdentityKey,
+    Host,
+    Profile,
+    Follow,
+    Follower,
+    Player,
+    Other,
+}
+
+fn classify_link_tag(tag: &LinkTag) -> LinkTagKind {
+    if *tag == LinkTag::new(String::from(IDENTITY_KEY_LINK_TAG)) {
+        LinkTagKind::IdentityKey
+    } else if *tag == LinkTag::new(String::from(HOST_LINK_TAG)) {
+        LinkTagKind::Host
+    } else if *tag == LinkTag::new(String::from(PROFILE_LINK_TAG)) {
+        LinkTagKind::Profile
+    } else if *tag == LinkTag::new(String::from(FOLLOW_LINK_TAG)) {
+        LinkTagKind::Follow
+    } else if *tag == LinkTag::new(String::from(FOLLOWER_LINK_TAG)) {
+        LinkTagKind::Follower
+    } else if *tag == LinkTag::new(String::from(PLAYER_LINK_TAG)) {
+        LinkTagKind::Player
+    } else {
+        LinkTagKind::Other
+    }
+}
+
+/// Dispatches validation for every header this zome module is responsible
+/// for. lib.rs's `validate` callback forwards here (and to the equivalent
+/// dispatch fns in sibling modules) so each module only has to know about
+/// its own entry/link shapes.
+pub fn validate(validate_data: ValidateData) -> ExternResult<ValidateCallbackResult> {
+    match validate_data.element.header() {
+        Header::Create(_) | Header::Update(_) => {
+            validate_create_entry_player_profile(validate_data)
+        }
+        Header::CreateLink(create_link) => {
+            let tag_kind = classify_link_tag(&create_link.tag);
+            let base_address = create_link.base_address.clone();
+            match tag_kind {
+                LinkTagKind::IdentityKey => validate_create_link_identity_key(validate_data),
+                LinkTagKind::Host => validate_create_link_host(validate_data),
+                LinkTagKind::Profile => validate_create_link_profile(validate_data),
+                LinkTagKind::Follow => validate_create_link_follow(validate_data),
+                LinkTagKind::Follower => validate_create_link_follower(validate_data),
+                LinkTagKind::Player => validate_create_link_player_profile(validate_data),
+                // Only reached for tags that aren't one of the named link
+                // kinds above, so this is the only branch that ever needs to
+                // hash the games registry anchor - never on the PLAYER-tag
+                // hot path.
+                LinkTagKind::Other => {
+                    if base_address == games_registry_anchor_hash()?.into() {
+                        validate_create_link_game_registry(validate_data)
+                    } else {
+                        validate_create_link_player_profile(validate_data)
+                    }
+                }
+            }
+        }
+        _ => Ok(ValidateCallbackResult::Valid),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixt::prelude::*;
+
+    #[test]
+    fn chain_author_call_is_always_authorized() {
+        let author = fixt!(AgentPubKey);
+        assert!(cap_grant_authorizes(
+            &CapGrant::ChainAuthor(author),
+            HOST_CAP_GRANT_TAG
+        ));
+    }
+
+    #[test]
+    fn remote_call_needs_a_grant_tagged_for_this_privileged_action() {
+        let host_grant = ZomeCallCapGrant {
+            tag: String::from(HOST_CAP_GRANT_TAG),
+            access: CapAccess::Unrestricted,
+            functions: GrantedFunctions::All,
+        };
+        assert!(cap_grant_authorizes(
+            &CapGrant::RemoteAgent(host_grant.clone()),
+            HOST_CAP_GRANT_TAG
+        ));
+
+        let unrelated_grant = ZomeCallCapGrant {
+            tag: String::from("some other capability"),
+            ..host_grant
+        };
+        assert!(!cap_grant_authorizes(
+            &CapGrant::RemoteAgent(unrelated_grant),
+            HOST_CAP_GRANT_TAG
+        ));
+    }
+
+    #[test]
+    fn player_link_tag_classifies_ahead_of_the_registry_anchor_branch() {
+        // This is the regression chunk0-6's fix closes: PLAYER_LINK_TAG -
+        // the tag join_game creates on every single player join - must
+        // classify as its own kind, not fall through to Other, which is the
+        // only branch `validate` hashes the games registry anchor from.
+        assert_eq!(
+            classify_link_tag(&LinkTag::new(String::from(PLAYER_LINK_TAG))),
+            LinkTagKind::Player
+        );
+    }
+
+    #[test]
+    fn named_link_tags_all_classify_before_other() {
+        for tag in [
+            IDENTITY_KEY_LINK_TAG,
+            HOST_LINK_TAG,
+            PROFILE_LINK_TAG,
+            FOLLOW_LINK_TAG,
+            FOLLOWER_LINK_TAG,
+            PLAYER_LINK_TAG,
+        ] {
+            assert_ne!(
+                classify_link_tag(&LinkTag::new(String::from(tag))),
+                LinkTagKind::Other,
+                "{} must not classify as Other",
+                tag
+            );
+        }
+    }
+
+    #[test]
+    fn an_arbitrary_game_code_tag_classifies_as_other() {
+        // Games registry links are tagged with the raw game code, which
+        // can't be matched against a fixed constant - Other is the only
+        // classification they can fall into, which is exactly why that
+        // branch (and only that branch) needs the anchor-hash check.
+        assert_eq!(
+            classify_link_tag(&LinkTag::new(b"some-game-code".to_vec())),
+            LinkTagKind::Other
+        );
+    }
+}